@@ -7,12 +7,15 @@
 #include <unistd.h>
 #include "fcntl.h"
 #include "string.h"
+#include <strings.h>
 #include "fuse.h"
 #include <stddef.h>
 #include "ddriver.h"
 #include "errno.h"
 #include "types.h"
 #include "stdint.h"
+#include <endian.h>
+#include <time.h>
 
 
 /******************************************************************************
@@ -24,8 +27,11 @@
 *******************************************************************************/
 char* 			   sfs_get_fname(const char* path);
 int 			   sfs_calc_lvl(const char * path);
+boolean 		   sfs_fname_eq(const char* fname, const char* name);
+void 			   sfs_io_delay(int delay_ms, size_t size);
 int 			   sfs_driver_read(int offset, uint8_t *out_content, int size);
 int 			   sfs_driver_write(int offset, uint8_t *in_content, int size);
+int 			   sfs_driver_write_meta(int offset, uint8_t *in_content, int size);
 
 
 int 			   sfs_mount(struct custom_options options);
@@ -38,6 +44,8 @@ int 			   sfs_sync_inode(struct sfs_inode * inode);
 int 			   sfs_drop_inode(struct sfs_inode * inode);
 struct sfs_inode*  sfs_read_inode(struct sfs_dentry * dentry, int ino);
 struct sfs_dentry* sfs_get_dentry(struct sfs_inode * inode, int dir);
+int 			   sfs_calc_nlink(struct sfs_inode * inode);
+void 			   sfs_prewarm(struct sfs_inode * inode);
 
 struct sfs_dentry* sfs_lookup(const char * path, boolean * is_find, boolean* is_root);
 /******************************************************************************
@@ -59,6 +67,7 @@ int   			   sfs_rmdir(const char *);
 int   			   sfs_rename(const char *, const char *);
 int   			   sfs_utimens(const char *, const struct timespec tv[2]);
 int   			   sfs_truncate(const char *, off_t);
+int   			   sfs_statfs(const char *, struct statvfs *);
 int 			   sfs_symlink(const char *, const char *);
 int 			   sfs_readlink(const char *, char *, size_t);
 			
@@ -36,6 +36,9 @@ typedef enum sfs_file_type {
 #define SFS_ERROR_UNSUPPORTED   ENXIO
 #define SFS_ERROR_IO            EIO     /* Error Input/Output */
 #define SFS_ERROR_INVAL         EINVAL  /* Invalid Args */
+#define SFS_ERROR_NAMETOOLONG   ENAMETOOLONG
+#define SFS_ERROR_NOTEMPTY      ENOTEMPTY
+#define SFS_ERROR_FBIG          EFBIG   /* File too large */
 
 #define SFS_MAX_FILE_NAME       128
 #define SFS_INODE_PER_FILE      1
@@ -53,6 +56,8 @@ typedef enum sfs_file_type {
 #define SFS_IO_SZ()                     (sfs_super.sz_io)
 #define SFS_DISK_SZ()                   (sfs_super.sz_disk)
 #define SFS_DRIVER()                    (sfs_super.driver_fd)
+#define SFS_MAX_FILE_SZ()               (sfs_super.max_file_sz)
+#define SFS_MAX_DENTRY_CNT()            ((int)(SFS_BLKS_SZ(SFS_DATA_PER_FILE) / sizeof(struct sfs_dentry_d)))
 
 #define SFS_ROUND_DOWN(value, round)    ((value) % (round) == 0 ? (value) : ((value) / (round)) * (round))
 #define SFS_ROUND_UP(value, round)      ((value) % (round) == 0 ? (value) : ((value) / (round) + 1) * (round))
@@ -77,6 +82,15 @@ struct sfs_super;
 struct custom_options {
 	const char*        device;
 	boolean            show_help;
+	boolean            case_insensitive;              /* 文件名比较忽略大小写 */
+	int                max_file_size;                 /* 文件大小上限，0表示数据块容量 */
+	int                read_delay_ms;                 /* 每读一个IO块的延时(ms)，0不延时 */
+	int                write_delay_ms;                /* 每写一个IO块的延时(ms)，0不延时 */
+	int                fault_inject;                  /* write以该百分比概率返回EIO，0关闭 */
+	int                io_block_size;                 /* 报告的st_blksize，0表示IO块大小 */
+	boolean            prewarm;                       /* 挂载时预读所有inode */
+	boolean            verify_writes;                 /* 元数据写入后读回校验 */
+	long               fixed_time;                    /* 报告的时间戳(epoch秒)，0表示当前时间 */
 };
 
 struct sfs_inode
@@ -107,8 +121,11 @@ struct sfs_super
     int                sz_io;
     int                sz_disk;
     int                sz_usage;
+    int                max_file_sz;                   /* 不超过SFS_DATA_PER_FILE个块 */
     
     int                max_ino;
+    int                ino_cursor;                    /* 下次分配从此ino开始扫描 */
+    int                free_ino;                      /* 空闲inode数，随分配/释放更新 */
     uint8_t*           map_inode;
     int                map_inode_blks;
     int                map_inode_offset;
@@ -16,6 +16,15 @@ static const struct fuse_opt option_spec[] = {
 	OPTION("--device=%s", device),
 	OPTION("-h", show_help),
 	OPTION("--help", show_help),
+	OPTION("--case-insensitive", case_insensitive),
+	OPTION("--max-file-size=%d", max_file_size),
+	OPTION("--read-delay-ms=%d", read_delay_ms),
+	OPTION("--write-delay-ms=%d", write_delay_ms),
+	OPTION("--fault-inject=%d", fault_inject),
+	OPTION("--io-block-size=%d", io_block_size),
+	OPTION("--prewarm", prewarm),
+	OPTION("--verify-writes", verify_writes),
+	OPTION("--fixed-time=%ld", fixed_time),
 	FUSE_OPT_END
 };
 
@@ -35,6 +44,7 @@ static struct fuse_operations operations = {
 	.rename = sfs_rename,							  /* 重命名，mv */
 	.readlink = sfs_readlink,						  /* 读链接 */
 	.symlink = sfs_symlink,							  /* 软链接 */
+	.statfs = sfs_statfs,							  /* 容量统计，df */
 
 	.open = sfs_open,							
 	.opendir = sfs_opendir,
@@ -84,9 +94,19 @@ int sfs_mkdir(const char* path, mode_t mode) {
 	}
 
 	fname  = sfs_get_fname(path);
+	if (strlen(fname) >= SFS_MAX_FILE_NAME) {		  /* fname需以'\0'结尾 */
+		return -SFS_ERROR_NAMETOOLONG;
+	}
+	if (last_dentry->inode->dir_cnt >= SFS_MAX_DENTRY_CNT()) {  /* 目录数据块已满 */
+		return -SFS_ERROR_NOSPACE;
+	}
 	dentry = new_dentry(fname, SFS_DIR); 
 	dentry->parent = last_dentry;
 	inode  = sfs_alloc_inode(dentry);
+	if (inode == NULL) {							  /* inode已用尽 */
+		free(dentry);
+		return -SFS_ERROR_NOSPACE;
+	}
 	sfs_alloc_dentry(last_dentry->inode, dentry);
 	
 	return SFS_ERROR_NONE;
@@ -105,6 +125,10 @@ int sfs_getattr(const char* path, struct stat * sfs_stat) {
 		return -SFS_ERROR_NOTFOUND;
 	}
 
+	if (dentry->inode == NULL) {					  /* 磁盘上的inode已损坏 */
+		return -SFS_ERROR_IO;
+	}
+
 	if (SFS_IS_DIR(dentry->inode)) {
 		sfs_stat->st_mode = S_IFDIR | SFS_DEFAULT_PERM;
 		sfs_stat->st_size = dentry->inode->dir_cnt * sizeof(struct sfs_dentry_d);
@@ -118,18 +142,14 @@ int sfs_getattr(const char* path, struct stat * sfs_stat) {
 		sfs_stat->st_size = dentry->inode->size;
 	}
 
-	sfs_stat->st_nlink = 1;
+	sfs_stat->st_nlink = sfs_calc_nlink(dentry->inode);
 	sfs_stat->st_uid 	 = getuid();
 	sfs_stat->st_gid 	 = getgid();
-	sfs_stat->st_atime   = time(NULL);
-	sfs_stat->st_mtime   = time(NULL);
-	sfs_stat->st_blksize = SFS_IO_SZ();
-
-	if (is_root) {
-		sfs_stat->st_size	= sfs_super.sz_usage; 
-		sfs_stat->st_blocks = SFS_DISK_SZ() / SFS_IO_SZ();
-		sfs_stat->st_nlink  = 2;		/* !特殊，根目录link数为2 */
-	}
+	sfs_stat->st_atime   = sfs_options.fixed_time ? sfs_options.fixed_time : time(NULL);
+	sfs_stat->st_mtime   = sfs_stat->st_atime;
+	sfs_stat->st_ctime   = sfs_stat->st_atime;
+	sfs_stat->st_blksize = sfs_options.io_block_size > 0 ? sfs_options.io_block_size : SFS_IO_SZ();
+	sfs_stat->st_blocks  = SFS_ROUND_UP(sfs_stat->st_size, 512) / 512;   /* st_blocks以512字节计 */
 	return SFS_ERROR_NONE;
 }
 /**
@@ -189,6 +209,12 @@ int sfs_mknod(const char* path, mode_t mode, dev_t dev) {
 	}
 
 	fname = sfs_get_fname(path);
+	if (strlen(fname) >= SFS_MAX_FILE_NAME) {		  /* fname需以'\0'结尾 */
+		return -SFS_ERROR_NAMETOOLONG;
+	}
+	if (last_dentry->inode->dir_cnt >= SFS_MAX_DENTRY_CNT()) {  /* 目录数据块已满 */
+		return -SFS_ERROR_NOSPACE;
+	}
 	
 	if (S_ISREG(mode)) {
 		dentry = new_dentry(fname, SFS_REG_FILE);
@@ -201,6 +227,10 @@ int sfs_mknod(const char* path, mode_t mode, dev_t dev) {
 	}
 	dentry->parent = last_dentry;
 	inode = sfs_alloc_inode(dentry);
+	if (inode == NULL) {							  /* inode已用尽 */
+		free(dentry);
+		return -SFS_ERROR_NOSPACE;
+	}
 	sfs_alloc_dentry(last_dentry->inode, dentry);
 
 	return SFS_ERROR_NONE;
@@ -231,12 +261,31 @@ int sfs_write(const char* path, const char* buf, size_t size, off_t offset,
 		return -SFS_ERROR_ISDIR;	
 	}
 
-	if (inode->size < offset) {
-		return -SFS_ERROR_SEEK;
+	if (sfs_options.fault_inject > 0 && 				  /* 故障注入，不修改文件内容 */
+		rand() % 100 < sfs_options.fault_inject) {
+		return -SFS_ERROR_IO;
+	}
+
+	if (offset < 0) {
+		return -SFS_ERROR_INVAL;
+	}
+
+	if (offset >= SFS_MAX_FILE_SZ()) {				  /* 起点已超出文件大小上限 */
+		return -SFS_ERROR_FBIG;
+	}
+
+	if (size > SFS_MAX_FILE_SZ() - offset) {		  /* 只写入上限以内的部分 */
+		size = SFS_MAX_FILE_SZ() - offset;
+	}
+
+	if (inode->size < offset) {						  /* 跳过的空洞读出为0 */
+		memset(inode->data + inode->size, 0, offset - inode->size);
 	}
 
 	memcpy(inode->data + offset, buf, size);
 	inode->size = offset + size > inode->size ? offset + size : inode->size;
+
+	sfs_io_delay(sfs_options.write_delay_ms, size);	  /* --write-delay-ms，默认不延时 */
 	
 	return size;
 }
@@ -266,12 +315,22 @@ int sfs_read(const char* path, char* buf, size_t size, off_t offset,
 		return -SFS_ERROR_ISDIR;	
 	}
 
-	if (inode->size < offset) {
-		return -SFS_ERROR_SEEK;
+	if (offset < 0) {
+		return -SFS_ERROR_INVAL;
+	}
+
+	if (offset >= inode->size) {					  /* 文件末尾之后读到0字节 */
+		return 0;
+	}
+
+	if (size > inode->size - offset) {				  /* 只读到文件末尾 */
+		size = inode->size - offset;
 	}
 
 	memcpy(buf, inode->data + offset, size);
 
+	sfs_io_delay(sfs_options.read_delay_ms, size);	  /* --read-delay-ms，默认不延时 */
+
 	return size;			   
 }
 /**
@@ -304,6 +363,16 @@ int sfs_unlink(const char* path) {
  * @return int 
  */
 int sfs_rmdir(const char* path) {
+	boolean	is_find, is_root;
+	struct sfs_dentry* dentry = sfs_lookup(path, &is_find, &is_root);
+
+	if (is_find == FALSE) {
+		return -SFS_ERROR_NOTFOUND;
+	}
+
+	if (dentry->inode->dir_cnt != 0) {				  /* 只删除空目录 */
+		return -SFS_ERROR_NOTEMPTY;
+	}
 	return sfs_unlink(path);
 }
 /**
@@ -320,6 +389,7 @@ int sfs_rename(const char* from, const char* to) {
 	struct sfs_inode*  from_inode;
 	struct sfs_dentry* to_dentry;
 	mode_t mode = 0;
+	boolean is_detached = FALSE;
 	if (is_find == FALSE) {
 		return -SFS_ERROR_NOTFOUND;
 	}
@@ -337,8 +407,18 @@ int sfs_rename(const char* from, const char* to) {
 		mode = S_IFREG;
 	}
 	
+	to_dentry = sfs_lookup(to, &is_find, &is_root);	  /* 未找到时为目的父目录 */
+	if (!is_find && to_dentry == from_dentry->parent) {  /* 同目录改名，先腾出原目录项 */
+		sfs_drop_dentry(from_dentry->parent->inode, from_dentry);
+		is_detached = TRUE;
+	}
+
 	ret = sfs_mknod(to, mode, NULL);
 	if (ret != SFS_ERROR_NONE) {					  /* 保证目的文件不存在 */
+		if (is_detached) {							  /* 放回原目录项 */
+			from_dentry->brother = NULL;
+			sfs_alloc_dentry(from_dentry->parent->inode, from_dentry);
+		}
 		return ret;
 	}
 	
@@ -347,7 +427,9 @@ int sfs_rename(const char* from, const char* to) {
 	to_dentry->ino = from_inode->ino;				  /* 指向新的inode */
 	to_dentry->inode = from_inode;
 	
-	sfs_drop_dentry(from_dentry->parent->inode, from_dentry);
+	if (!is_detached) {
+		sfs_drop_dentry(from_dentry->parent->inode, from_dentry);
+	}
 	return ret;
 }
 /**
@@ -360,14 +442,20 @@ int sfs_rename(const char* from, const char* to) {
 int sfs_symlink(const char* path, const char* link){
 	int ret = SFS_ERROR_NONE;
 	boolean	is_find, is_root;
+	if (strlen(path) >= SFS_MAX_FILE_NAME) {		/* target_path定长，需容纳'\0' */
+		return -SFS_ERROR_NAMETOOLONG;
+	}
 	ret = sfs_mknod(link, S_IFREG, NULL);
+	if (ret != SFS_ERROR_NONE) {
+		return ret;
+	}
 	struct sfs_dentry* dentry = sfs_lookup(link, &is_find, &is_root);
 	if (is_find == FALSE) {
 		return -SFS_ERROR_NOTFOUND;
 	}
 	dentry->ftype = SFS_SYM_LINK;
 	struct sfs_inode* inode = dentry->inode;
-	memcpy(inode->target_path, path, SFS_MAX_FILE_NAME);
+	memcpy(inode->target_path, path, strlen(path) + 1);
 	return ret;
 }
 /**
@@ -491,10 +579,43 @@ int sfs_truncate(const char* path, off_t offset) {
 		return -SFS_ERROR_ISDIR;
 	}
 
+	if (offset < 0) {
+		return -SFS_ERROR_INVAL;
+	}
+
+	if (offset > SFS_MAX_FILE_SZ()) {				  /* 超出文件大小上限 */
+		return -SFS_ERROR_FBIG;
+	}
+
+	if (inode->size < offset) {						  /* 扩展部分读出为0 */
+		memset(inode->data + inode->size, 0, offset - inode->size);
+	}
 	inode->size = offset;
 
 	return SFS_ERROR_NONE;
 }
+/**
+ * @brief 文件系统容量，每个inode固定占用SFS_INODE_PER_FILE + SFS_DATA_PER_FILE个块
+ * 
+ * @param path 
+ * @param sfs_statvfs 
+ * @return int 
+ */
+int sfs_statfs(const char* path, struct statvfs * sfs_statvfs) {
+	(void)path;
+	int blks_per_ino = SFS_INODE_PER_FILE + SFS_DATA_PER_FILE;
+
+	sfs_statvfs->f_bsize   = SFS_IO_SZ();
+	sfs_statvfs->f_frsize  = SFS_IO_SZ();
+	sfs_statvfs->f_blocks  = sfs_super.max_ino * blks_per_ino;
+	sfs_statvfs->f_bfree   = sfs_super.free_ino * blks_per_ino;
+	sfs_statvfs->f_bavail  = sfs_statvfs->f_bfree;
+	sfs_statvfs->f_files   = sfs_super.max_ino;
+	sfs_statvfs->f_ffree   = sfs_super.free_ino;
+	sfs_statvfs->f_favail  = sfs_super.free_ino;
+	sfs_statvfs->f_namemax = SFS_MAX_FILE_NAME - 1;
+	return SFS_ERROR_NONE;
+}
 /**
  * @brief 展示sfs用法
  * 
@@ -505,8 +626,19 @@ void sfs_usage() {
 	printf("Author: Deadpool <deadpoolmine@qq.com>\n");
 	printf("Description: A Filesystem in UserSpacE (FUSE) sample file system \n");
 	printf("\n");
-	printf("Usage: ./sfs-fuse --device=[device path] mntpoint\n");
+	printf("Usage: ./sfs-fuse --device=[device path] [options] mntpoint\n");
 	printf("mount device to mntpoint with SFS\n");
+	printf("\n");
+	printf("SFS options\n");
+	printf("    --case-insensitive     compare file names ignoring case\n");
+	printf("    --max-file-size=N      limit files to N bytes (at most %d blocks)\n", SFS_DATA_PER_FILE);
+	printf("    --read-delay-ms=N      sleep N ms per I/O unit read\n");
+	printf("    --write-delay-ms=N     sleep N ms per I/O unit written\n");
+	printf("    --fault-inject=N       fail N%% of writes with EIO\n");
+	printf("    --io-block-size=N      advertise N bytes as st_blksize\n");
+	printf("    --prewarm              load every inode at mount\n");
+	printf("    --verify-writes        read back and compare metadata writes\n");
+	printf("    --fixed-time=EPOCH     report EPOCH as every timestamp\n");
 	printf("=================================================================\n");
 	printf("FUSE general options\n");
 	return;
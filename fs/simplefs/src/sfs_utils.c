@@ -37,6 +37,37 @@ int sfs_calc_lvl(const char * path) {
     }
     return lvl;
 }
+/**
+ * @brief 比较dentry名与路径分量，--case-insensitive时忽略大小写
+ * 
+ * @param fname dentry中保存的文件名
+ * @param name 
+ * @return boolean 
+ */
+boolean sfs_fname_eq(const char* fname, const char* name) {
+    if (sfs_options.case_insensitive) {
+        return strncasecmp(fname, name, SFS_MAX_FILE_NAME) == 0;
+    }
+    return strncmp(fname, name, SFS_MAX_FILE_NAME) == 0;
+}
+/**
+ * @brief 按传输的IO块数延时，模拟慢速设备
+ * 
+ * @param delay_ms 每个IO块的延时(ms)
+ * @param size 传输的字节数
+ * @return void
+ */
+void sfs_io_delay(int delay_ms, size_t size) {
+    long            total_ms = (long)delay_ms * (SFS_ROUND_UP(size, SFS_IO_SZ()) / SFS_IO_SZ());
+    struct timespec delay;
+
+    if (total_ms <= 0) {
+        return;
+    }
+    delay.tv_sec  = total_ms / 1000;
+    delay.tv_nsec = (total_ms % 1000) * 1000000;
+    nanosleep(&delay, NULL);
+}
 /**
  * @brief 驱动读
  * 
@@ -52,11 +83,17 @@ int sfs_driver_read(int offset, uint8_t *out_content, int size) {
     uint8_t* temp_content   = (uint8_t*)malloc(size_aligned);
     uint8_t* cur            = temp_content;
     // lseek(SFS_DRIVER(), offset_aligned, SEEK_SET);
-    ddriver_seek(SFS_DRIVER(), offset_aligned, SEEK_SET);
+    if (ddriver_seek(SFS_DRIVER(), offset_aligned, SEEK_SET) < 0) {
+        free(temp_content);
+        return -SFS_ERROR_IO;
+    }
     while (size_aligned != 0)
     {
         // read(SFS_DRIVER(), cur, SFS_IO_SZ());
-        ddriver_read(SFS_DRIVER(), cur, SFS_IO_SZ());
+        if (ddriver_read(SFS_DRIVER(), (char *)cur, SFS_IO_SZ()) < 0) {
+            free(temp_content);                       /* 读失败不返回不完整的数据 */
+            return -SFS_ERROR_IO;
+        }
         cur          += SFS_IO_SZ();
         size_aligned -= SFS_IO_SZ();   
     }
@@ -78,15 +115,24 @@ int sfs_driver_write(int offset, uint8_t *in_content, int size) {
     int      size_aligned   = SFS_ROUND_UP((size + bias), SFS_IO_SZ());
     uint8_t* temp_content   = (uint8_t*)malloc(size_aligned);
     uint8_t* cur            = temp_content;
-    sfs_driver_read(offset_aligned, temp_content, size_aligned);
+    if (sfs_driver_read(offset_aligned, temp_content, size_aligned) != SFS_ERROR_NONE) {
+        free(temp_content);
+        return -SFS_ERROR_IO;
+    }
     memcpy(temp_content + bias, in_content, size);
     
     // lseek(SFS_DRIVER(), offset_aligned, SEEK_SET);
-    ddriver_seek(SFS_DRIVER(), offset_aligned, SEEK_SET);
+    if (ddriver_seek(SFS_DRIVER(), offset_aligned, SEEK_SET) < 0) {
+        free(temp_content);
+        return -SFS_ERROR_IO;
+    }
     while (size_aligned != 0)
     {
         // write(SFS_DRIVER(), cur, SFS_IO_SZ());
-        ddriver_write(SFS_DRIVER(), cur, SFS_IO_SZ());
+        if (ddriver_write(SFS_DRIVER(), (char *)cur, SFS_IO_SZ()) < 0) {
+            free(temp_content);                       /* 写失败需上报，不能当作已落盘 */
+            return -SFS_ERROR_IO;
+        }
         cur          += SFS_IO_SZ();
         size_aligned -= SFS_IO_SZ();   
     }
@@ -94,6 +140,34 @@ int sfs_driver_write(int offset, uint8_t *in_content, int size) {
     free(temp_content);
     return SFS_ERROR_NONE;
 }
+/**
+ * @brief 写元数据，--verify-writes时读回比较，发现介质静默损坏写入
+ * 
+ * @param offset 
+ * @param in_content 
+ * @param size 
+ * @return int 
+ */
+int sfs_driver_write_meta(int offset, uint8_t *in_content, int size) {
+    uint8_t* check_content;
+    int      ret = SFS_ERROR_NONE;
+    if (sfs_driver_write(offset, in_content, size) != SFS_ERROR_NONE) {
+        return -SFS_ERROR_IO;
+    }
+    if (!sfs_options.verify_writes) {
+        return SFS_ERROR_NONE;
+    }
+    check_content = (uint8_t*)malloc(size);
+    if (sfs_driver_read(offset, check_content, size) != SFS_ERROR_NONE) {
+        ret = -SFS_ERROR_IO;
+    }
+    else if (memcmp(check_content, in_content, size) != 0) {
+        fprintf(stderr, "SFS: write verification failed at offset %d, size %d\n", offset, size);
+        ret = -SFS_ERROR_IO;
+    }
+    free(check_content);
+    return ret;
+}
 /**
  * @brief 为一个inode分配dentry，采用头插法
  * 
@@ -149,36 +223,35 @@ int sfs_drop_dentry(struct sfs_inode * inode, struct sfs_dentry * dentry) {
  * @brief 分配一个inode，占用位图
  * 
  * @param dentry 该dentry指向分配的inode
- * @return sfs_inode
+ * @return sfs_inode, inode已用尽时返回NULL
  */
 struct sfs_inode* sfs_alloc_inode(struct sfs_dentry * dentry) {
     struct sfs_inode* inode;
     int byte_cursor = 0; 
     int bit_cursor  = 0; 
     int ino_cursor  = 0;
+    int i;
     boolean is_find_free_entry = FALSE;
-
-    for (byte_cursor = 0; byte_cursor < SFS_BLKS_SZ(sfs_super.map_inode_blks); 
-         byte_cursor++)
-    {
-        for (bit_cursor = 0; bit_cursor < UINT8_BITS; bit_cursor++) {
-            if((sfs_super.map_inode[byte_cursor] & (0x1 << bit_cursor)) == 0) {    
+                                                      /* 从上次分配的位置开始回绕扫描 */
+    for (i = 0; i < sfs_super.max_ino; i++) {
+        ino_cursor  = (sfs_super.ino_cursor + i) % sfs_super.max_ino;
+        byte_cursor = ino_cursor / UINT8_BITS;
+        bit_cursor  = ino_cursor % UINT8_BITS;
+        if((sfs_super.map_inode[byte_cursor] & (0x1 << bit_cursor)) == 0) {    
                                                       /* 当前ino_cursor位置空闲 */
-                sfs_super.map_inode[byte_cursor] |= (0x1 << bit_cursor);
-                is_find_free_entry = TRUE;           
-                break;
-            }
-            ino_cursor++;
-        }
-        if (is_find_free_entry) {
+            is_find_free_entry = TRUE;           
             break;
         }
     }
 
-    if (!is_find_free_entry || ino_cursor == sfs_super.max_ino)
-        return -SFS_ERROR_NOSPACE;
+    if (!is_find_free_entry)
+        return NULL;
+                                                      /* 确认可分配后再占用位图 */
+    sfs_super.map_inode[byte_cursor] |= (0x1 << bit_cursor);
+    sfs_super.ino_cursor = ino_cursor + 1;
+    sfs_super.free_ino--;
 
-    inode = (struct sfs_inode*)malloc(sizeof(struct sfs_inode));
+    inode = (struct sfs_inode*)calloc(1, sizeof(struct sfs_inode));
     inode->ino  = ino_cursor; 
     inode->size = 0;
                                                       /* dentry指向inode */
@@ -191,7 +264,7 @@ struct sfs_inode* sfs_alloc_inode(struct sfs_dentry * dentry) {
     inode->dentrys = NULL;
     
     if (SFS_IS_REG(inode)) {
-        inode->data = (uint8_t *)malloc(SFS_BLKS_SZ(SFS_DATA_PER_FILE));
+        inode->data = (uint8_t *)calloc(1, SFS_BLKS_SZ(SFS_DATA_PER_FILE));
     }
 
     return inode;
@@ -207,14 +280,15 @@ int sfs_sync_inode(struct sfs_inode * inode) {
     struct sfs_dentry*  dentry_cursor;
     struct sfs_dentry_d dentry_d;
     int ino             = inode->ino;
-    inode_d.ino         = ino;
-    inode_d.size        = inode->size;
+    int ret             = SFS_ERROR_NONE;
+    inode_d.ino         = htole32(ino);                /* 磁盘结构统一按小端存储 */
+    inode_d.size        = htole32(inode->size);
     memcpy(inode_d.target_path, inode->target_path, SFS_MAX_FILE_NAME);
-    inode_d.ftype       = inode->dentry->ftype;
-    inode_d.dir_cnt     = inode->dir_cnt;
+    inode_d.ftype       = htole32(inode->dentry->ftype);
+    inode_d.dir_cnt     = htole32(inode->dir_cnt);
     int offset;
     
-    if (sfs_driver_write(SFS_INO_OFS(ino), (uint8_t *)&inode_d, 
+    if (sfs_driver_write_meta(SFS_INO_OFS(ino), (uint8_t *)&inode_d, 
                      sizeof(struct sfs_inode_d)) != SFS_ERROR_NONE) {
         SFS_DBG("[%s] io error\n", __func__);
         return -SFS_ERROR_IO;
@@ -227,16 +301,17 @@ int sfs_sync_inode(struct sfs_inode * inode) {
         while (dentry_cursor != NULL)
         {
             memcpy(dentry_d.fname, dentry_cursor->fname, SFS_MAX_FILE_NAME);
-            dentry_d.ftype = dentry_cursor->ftype;
-            dentry_d.ino = dentry_cursor->ino;
-            if (sfs_driver_write(offset, (uint8_t *)&dentry_d, 
+            dentry_d.ftype = htole32(dentry_cursor->ftype);
+            dentry_d.ino = htole32(dentry_cursor->ino);
+            if (sfs_driver_write_meta(offset, (uint8_t *)&dentry_d, 
                                  sizeof(struct sfs_dentry_d)) != SFS_ERROR_NONE) {
                 SFS_DBG("[%s] io error\n", __func__);
                 return -SFS_ERROR_IO;                     
             }
             
-            if (dentry_cursor->inode != NULL) {
-                sfs_sync_inode(dentry_cursor->inode);
+            if (dentry_cursor->inode != NULL &&       /* 其余子节点照常刷写，错误向上传递 */
+                sfs_sync_inode(dentry_cursor->inode) != SFS_ERROR_NONE) {
+                ret = -SFS_ERROR_IO;
             }
 
             dentry_cursor = dentry_cursor->brother;
@@ -250,7 +325,7 @@ int sfs_sync_inode(struct sfs_inode * inode) {
             return -SFS_ERROR_IO;
         }
     }
-    return SFS_ERROR_NONE;
+    return ret;
 }
 /**
  * @brief 删除内存中的一个inode， 暂时不释放
@@ -262,7 +337,7 @@ int sfs_sync_inode(struct sfs_inode * inode) {
  *                       |
  *                      Inode  (Reg File)
  * 
- *  1) Step 1. Erase Bitmap     
+ *  1) Step 1. Erase Bitmap                    (Dir as well)
  *  2) Step 2. Free Inode                      (Function of sfs_drop_inode)
  * ------------------------------------------------------------------------
  *  3) *Setp 3. Free Dentry belonging to Inode (Outsider)
@@ -285,14 +360,12 @@ int sfs_drop_inode(struct sfs_inode * inode) {
     struct sfs_dentry*  dentry_to_free;
     struct sfs_inode*   inode_cursor;
 
-    int byte_cursor = 0; 
-    int bit_cursor  = 0; 
-    int ino_cursor  = 0;
-    boolean is_find = FALSE;
-
     if (inode == sfs_super.root_dentry->inode) {
         return SFS_ERROR_INVAL;
     }
+                                                      /* 调整inodemap，目录同样需要释放 */
+    sfs_super.map_inode[inode->ino / UINT8_BITS] &= (uint8_t)(~(0x1 << (inode->ino % UINT8_BITS)));
+    sfs_super.free_ino++;
 
     if (SFS_IS_DIR(inode)) {
         dentry_cursor = inode->dentrys;
@@ -308,21 +381,6 @@ int sfs_drop_inode(struct sfs_inode * inode) {
         }
     }
     else if (SFS_IS_REG(inode) || SFS_IS_SYM_LINK(inode)) {
-        for (byte_cursor = 0; byte_cursor < SFS_BLKS_SZ(sfs_super.map_inode_blks); 
-            byte_cursor++)                            /* 调整inodemap */
-        {
-            for (bit_cursor = 0; bit_cursor < UINT8_BITS; bit_cursor++) {
-                if (ino_cursor == inode->ino) {
-                     sfs_super.map_inode[byte_cursor] &= (uint8_t)(~(0x1 << bit_cursor));
-                     is_find = TRUE;
-                     break;
-                }
-                ino_cursor++;
-            }
-            if (is_find == TRUE) {
-                break;
-            }
-        }
         if (inode->data)
             free(inode->data);
         free(inode);
@@ -334,11 +392,11 @@ int sfs_drop_inode(struct sfs_inode * inode) {
  * 
  * @param dentry dentry指向ino，读取该inode
  * @param ino inode唯一编号
- * @return struct sfs_inode* 
+ * @return struct sfs_inode*, 读取失败或inode损坏时返回NULL
  */
 struct sfs_inode* sfs_read_inode(struct sfs_dentry * dentry, int ino) {
     printf("~ hello ~\n");
-    struct sfs_inode* inode = (struct sfs_inode*)malloc(sizeof(struct sfs_inode));
+    struct sfs_inode* inode = (struct sfs_inode*)calloc(1, sizeof(struct sfs_inode));
     struct sfs_inode_d inode_d;
     struct sfs_dentry* sub_dentry;
     struct sfs_dentry_d dentry_d;
@@ -349,14 +407,19 @@ struct sfs_inode* sfs_read_inode(struct sfs_dentry * dentry, int ino) {
         return NULL;                    
     }
     inode->dir_cnt = 0;
-    inode->ino = inode_d.ino;
-    inode->size = inode_d.size;
+    inode->ino = le32toh(inode_d.ino);
+    inode->size = le32toh(inode_d.size);
     memcpy(inode->target_path, inode_d.target_path, SFS_MAX_FILE_NAME);
     inode->dentry = dentry;
     inode->dentrys = NULL;
     if (SFS_IS_DIR(inode)) {
-        dir_cnt = inode_d.dir_cnt;
-        for (i = 0; i < dir_cnt; i++)
+        dir_cnt = le32toh(inode_d.dir_cnt);
+        if (dir_cnt > SFS_MAX_DENTRY_CNT()) {         /* 目录数据块放不下, inode已损坏 */
+            SFS_DBG("[%s] corrupted dir_cnt %d in ino %d\n", __func__, dir_cnt, ino);
+            free(inode);
+            return NULL;
+        }
+        for (i = dir_cnt - 1; i >= 0; i--)            /* 逆序头插，保持写回时的顺序 */
         {
             if (sfs_driver_read(SFS_DATA_OFS(ino) + i * sizeof(struct sfs_dentry_d), 
                                 (uint8_t *)&dentry_d, 
@@ -364,13 +427,30 @@ struct sfs_inode* sfs_read_inode(struct sfs_dentry * dentry, int ino) {
                 SFS_DBG("[%s] io error\n", __func__);
                 return NULL;                    
             }
-            sub_dentry = new_dentry(dentry_d.fname, dentry_d.ftype);
+            if (memchr(dentry_d.fname, '\0', SFS_MAX_FILE_NAME) == NULL ||
+                le32toh(dentry_d.ftype) > SFS_SYM_LINK || 
+                le32toh(dentry_d.ino) >= sfs_super.max_ino) {
+                SFS_DBG("[%s] corrupted dentry %d in ino %d\n", __func__, i, ino);
+                while (inode->dentrys) {              /* 释放已读出的目录项 */
+                    sub_dentry = inode->dentrys;
+                    inode->dentrys = sub_dentry->brother;
+                    free(sub_dentry);
+                }
+                free(inode);
+                return NULL;
+            }
+            sub_dentry = new_dentry(dentry_d.fname, le32toh(dentry_d.ftype));
             sub_dentry->parent = inode->dentry;
-            sub_dentry->ino    = dentry_d.ino; 
+            sub_dentry->ino    = le32toh(dentry_d.ino); 
             sfs_alloc_dentry(inode, sub_dentry);
         }
     }
     else if (SFS_IS_REG(inode)) {
+        if (inode->size > SFS_BLKS_SZ(SFS_DATA_PER_FILE)) {
+            SFS_DBG("[%s] corrupted size %d in ino %d\n", __func__, inode->size, ino);
+            free(inode);
+            return NULL;
+        }
         inode->data = (uint8_t *)malloc(SFS_BLKS_SZ(SFS_DATA_PER_FILE));
         if (sfs_driver_read(SFS_DATA_OFS(ino), (uint8_t *)inode->data, 
                             SFS_BLKS_SZ(SFS_DATA_PER_FILE)) != SFS_ERROR_NONE) {
@@ -400,6 +480,46 @@ struct sfs_dentry* sfs_get_dentry(struct sfs_inode * inode, int dir) {
     }
     return NULL;
 }
+/**
+ * @brief 计算硬链接数：目录为2('.'与父目录中的项) + 子目录数('..')，其余为1
+ * 
+ * @param inode 
+ * @return int 
+ */
+int sfs_calc_nlink(struct sfs_inode * inode) {
+    struct sfs_dentry* dentry_cursor = inode->dentrys;
+    int    nlink = 2;
+    if (!SFS_IS_DIR(inode)) {
+        return 1;
+    }
+    while (dentry_cursor)
+    {
+        if (dentry_cursor->ftype == SFS_DIR) {        /* 按目录项类型统计，无需读入子inode */
+            nlink++;
+        }
+        dentry_cursor = dentry_cursor->brother;
+    }
+    return nlink;
+}
+/**
+ * @brief 预读目录树，将inode全部读入内存(--prewarm)
+ * 
+ * @param inode 目录inode
+ * @return void
+ */
+void sfs_prewarm(struct sfs_inode * inode) {
+    struct sfs_dentry* dentry_cursor = inode->dentrys;
+    while (dentry_cursor)
+    {
+        if (dentry_cursor->inode == NULL) {           /* 读取失败的留到访问时报错 */
+            dentry_cursor->inode = sfs_read_inode(dentry_cursor, dentry_cursor->ino);
+        }
+        if (dentry_cursor->inode != NULL && SFS_IS_DIR(dentry_cursor->inode)) {
+            sfs_prewarm(dentry_cursor->inode);
+        }
+        dentry_cursor = dentry_cursor->brother;
+    }
+}
 /**
  * @brief 
  * path: /qwe/ad  total_lvl = 2,
@@ -423,7 +543,7 @@ struct sfs_dentry* sfs_lookup(const char * path, boolean* is_find, boolean* is_r
     int   lvl = 0;
     boolean is_hit;
     char* fname = NULL;
-    char* path_cpy = (char*)malloc(sizeof(path));
+    char* path_cpy = (char*)malloc(strlen(path) + 1);
     *is_root = FALSE;
     strcpy(path_cpy, path);
 
@@ -437,10 +557,15 @@ struct sfs_dentry* sfs_lookup(const char * path, boolean* is_find, boolean* is_r
     {   
         lvl++;
         if (dentry_cursor->inode == NULL) {           /* Cache机制 */
-            sfs_read_inode(dentry_cursor, dentry_cursor->ino);
+            dentry_cursor->inode = sfs_read_inode(dentry_cursor, dentry_cursor->ino);
         }
 
         inode = dentry_cursor->inode;
+        if (inode == NULL) {                          /* 读取失败，交由调用者处理 */
+            *is_find = FALSE;
+            dentry_ret = dentry_cursor;
+            break;
+        }
 
         if (SFS_IS_REG(inode) && lvl < total_lvl) {
             SFS_DBG("[%s] not a dir\n", __func__);
@@ -453,7 +578,7 @@ struct sfs_dentry* sfs_lookup(const char * path, boolean* is_find, boolean* is_r
 
             while (dentry_cursor)
             {
-                if (memcmp(dentry_cursor->fname, fname, strlen(fname)) == 0) {
+                if (sfs_fname_eq(dentry_cursor->fname, fname)) {
                     is_hit = TRUE;
                     break;
                 }
@@ -480,6 +605,7 @@ struct sfs_dentry* sfs_lookup(const char * path, boolean* is_find, boolean* is_r
         dentry_ret->inode = sfs_read_inode(dentry_ret, dentry_ret->ino);
     }
     
+    free(path_cpy);
     return dentry_ret;
 }
 /**
@@ -505,6 +631,7 @@ int sfs_mount(struct custom_options options){
     int                 map_inode_blks;
     
     int                 super_blks;
+    int                 ino;
     boolean             is_init = FALSE;
 
     sfs_super.is_mounted = FALSE;
@@ -526,18 +653,32 @@ int sfs_mount(struct custom_options options){
                         sizeof(struct sfs_super_d)) != SFS_ERROR_NONE) {
         return -SFS_ERROR_IO;
     }   
-                                                      /* 读取super */
-    if (sfs_super_d.magic_num != SFS_MAGIC_NUM) {     /* 幻数无 */
+                                                      /* 读取super, 磁盘上为小端 */
+    sfs_super_d.magic_num        = le32toh(sfs_super_d.magic_num);
+    sfs_super_d.sz_usage         = le32toh(sfs_super_d.sz_usage);
+    sfs_super_d.max_ino          = le32toh(sfs_super_d.max_ino);
+    sfs_super_d.map_inode_blks   = le32toh(sfs_super_d.map_inode_blks);
+    sfs_super_d.map_inode_offset = le32toh(sfs_super_d.map_inode_offset);
+    sfs_super_d.data_offset      = le32toh(sfs_super_d.data_offset);
+
                                                       /* 估算各部分大小 */
-        super_blks = SFS_ROUND_UP(sizeof(struct sfs_super_d), SFS_IO_SZ()) / SFS_IO_SZ();
+    super_blks = SFS_ROUND_UP(sizeof(struct sfs_super_d), SFS_IO_SZ()) / SFS_IO_SZ();
+
+    inode_num  =  SFS_DISK_SZ() / ((SFS_DATA_PER_FILE + SFS_INODE_PER_FILE) * SFS_IO_SZ());
 
-        inode_num  =  SFS_DISK_SZ() / ((SFS_DATA_PER_FILE + SFS_INODE_PER_FILE) * SFS_IO_SZ());
+    map_inode_blks = SFS_ROUND_UP((SFS_ROUND_UP(inode_num, UINT32_BITS) / UINT8_BITS), SFS_IO_SZ()) 
+                     / SFS_IO_SZ();
+                                                      /* 每次挂载都重新计算，不依赖磁盘上的值 */
+    sfs_super.max_ino = (inode_num - super_blks - map_inode_blks); 
+    sfs_super.ino_cursor = 0;                         /* 分配游标不落盘 */
 
-        map_inode_blks = SFS_ROUND_UP((SFS_ROUND_UP(inode_num, UINT32_BITS) / UINT8_BITS), SFS_IO_SZ()) 
-                         / SFS_IO_SZ();
-        
+    sfs_super.max_file_sz = SFS_BLKS_SZ(SFS_DATA_PER_FILE);
+    if (options.max_file_size > 0 && options.max_file_size < sfs_super.max_file_sz) {
+        sfs_super.max_file_sz = options.max_file_size;
+    }
+
+    if (sfs_super_d.magic_num != SFS_MAGIC_NUM) {     /* 幻数无 */
                                                       /* 布局layout */
-        sfs_super.max_ino = (inode_num - super_blks - map_inode_blks); 
         sfs_super_d.map_inode_offset = SFS_SUPER_OFS + SFS_BLKS_SZ(super_blks);
         sfs_super_d.data_offset = sfs_super_d.map_inode_offset + SFS_BLKS_SZ(map_inode_blks);
         sfs_super_d.map_inode_blks  = map_inode_blks;
@@ -558,8 +699,22 @@ int sfs_mount(struct custom_options options){
     }
 
     if (is_init) {                                    /* 分配根节点 */
+        memset(sfs_super.map_inode, 0,                /* 新格式化的介质，位图从空开始 */
+               SFS_BLKS_SZ(sfs_super_d.map_inode_blks));
         root_inode = sfs_alloc_inode(root_dentry);
-        sfs_sync_inode(root_inode);
+        if (root_inode == NULL) {
+            return -SFS_ERROR_NOSPACE;
+        }
+        if (sfs_sync_inode(root_inode) != SFS_ERROR_NONE) {
+            return -SFS_ERROR_IO;
+        }
+    }
+
+    sfs_super.free_ino = 0;                           /* 统计一次，之后随分配/释放更新 */
+    for (ino = 0; ino < sfs_super.max_ino; ino++) {
+        if ((sfs_super.map_inode[ino / UINT8_BITS] & (0x1 << (ino % UINT8_BITS))) == 0) {
+            sfs_super.free_ino++;
+        }
     }
     
     root_inode            = sfs_read_inode(root_dentry, SFS_ROOT_INO);
@@ -567,6 +722,10 @@ int sfs_mount(struct custom_options options){
     sfs_super.root_dentry = root_dentry;
     sfs_super.is_mounted  = TRUE;
 
+    if (options.prewarm && root_inode != NULL) {     /* 挂载时读入整棵目录树 */
+        sfs_prewarm(root_inode);
+    }
+
     sfs_dump_map();
     return ret;
 }
@@ -577,31 +736,36 @@ int sfs_mount(struct custom_options options){
  */
 int sfs_umount() {
     struct sfs_super_d  sfs_super_d; 
+    int                 ret = SFS_ERROR_NONE;
 
     if (!sfs_super.is_mounted) {
         return SFS_ERROR_NONE;
     }
 
-    sfs_sync_inode(sfs_super.root_dentry->inode);     /* 从根节点向下刷写节点 */
+    if (sfs_sync_inode(sfs_super.root_dentry->inode) != SFS_ERROR_NONE) {
+        SFS_DBG("[%s] io error\n", __func__);        /* 位图仍需写回，与已写入的目录项一致 */
+        ret = -SFS_ERROR_IO;
+    }
                                                     
-    sfs_super_d.magic_num           = SFS_MAGIC_NUM;
-    sfs_super_d.map_inode_blks      = sfs_super.map_inode_blks;
-    sfs_super_d.map_inode_offset    = sfs_super.map_inode_offset;
-    sfs_super_d.data_offset         = sfs_super.data_offset;
-    sfs_super_d.sz_usage            = sfs_super.sz_usage;
-
-    if (sfs_driver_write(SFS_SUPER_OFS, (uint8_t *)&sfs_super_d, 
+    sfs_super_d.magic_num           = htole32(SFS_MAGIC_NUM);   /* 磁盘结构统一按小端存储 */
+    sfs_super_d.max_ino             = htole32(sfs_super.max_ino);
+    sfs_super_d.map_inode_blks      = htole32(sfs_super.map_inode_blks);
+    sfs_super_d.map_inode_offset    = htole32(sfs_super.map_inode_offset);
+    sfs_super_d.data_offset         = htole32(sfs_super.data_offset);
+    sfs_super_d.sz_usage            = htole32(sfs_super.sz_usage);
+
+    if (sfs_driver_write_meta(SFS_SUPER_OFS, (uint8_t *)&sfs_super_d, 
                      sizeof(struct sfs_super_d)) != SFS_ERROR_NONE) {
         return -SFS_ERROR_IO;
     }
 
-    if (sfs_driver_write(sfs_super_d.map_inode_offset, (uint8_t *)(sfs_super.map_inode), 
-                         SFS_BLKS_SZ(sfs_super_d.map_inode_blks)) != SFS_ERROR_NONE) {
+    if (sfs_driver_write_meta(sfs_super.map_inode_offset, (uint8_t *)(sfs_super.map_inode), 
+                         SFS_BLKS_SZ(sfs_super.map_inode_blks)) != SFS_ERROR_NONE) {
         return -SFS_ERROR_IO;
     }
 
     free(sfs_super.map_inode);
     ddriver_close(SFS_DRIVER());
 
-    return SFS_ERROR_NONE;
+    return ret;
 }
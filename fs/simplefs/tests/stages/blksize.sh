@@ -0,0 +1,16 @@
+#!/bin/bash
+# --io-block-size: 只改变报告的st_blksize, 不影响磁盘布局
+
+mount_sfs
+touch "${MNTPOINT}"/file0
+
+TEST_CASE="blksize 1 - defaults to the I/O unit"
+check "$TEST_CASE" "os.stat('${MNTPOINT}/file0').st_blksize == 512"
+
+umount_sfs
+mount_sfs --io-block-size=131072
+
+TEST_CASE="blksize 2 - advertised st_blksize"
+check "$TEST_CASE" "os.stat('${MNTPOINT}/file0').st_blksize == 131072"
+
+umount_sfs
@@ -0,0 +1,21 @@
+#!/bin/bash
+# 目录大小随目录项增长, st_blocks以512字节计
+DENTRY_SZ=136
+
+mount_sfs
+mkdir "${MNTPOINT}"/dir0
+
+TEST_CASE="blocks 1 - empty directory"
+check "$TEST_CASE" "os.stat('${MNTPOINT}/dir0').st_size == 0"
+
+for i in $(seq 0 9); do touch "${MNTPOINT}"/dir0/file$i; done
+TEST_CASE="blocks 2 - directory size tracks entries"
+check "$TEST_CASE" "os.stat('${MNTPOINT}/dir0').st_size == 10 * ${DENTRY_SZ}"
+
+TEST_CASE="blocks 3 - st_blocks of the directory"
+check "$TEST_CASE" "os.stat('${MNTPOINT}/dir0').st_blocks == 3"
+
+TEST_CASE="blocks 4 - root directory counts its entry"
+check "$TEST_CASE" "os.stat('${MNTPOINT}').st_size == ${DENTRY_SZ}"
+
+umount_sfs
@@ -0,0 +1,24 @@
+#!/bin/bash
+# --case-insensitive: README与readme冲突, 默认不冲突
+
+mount_sfs
+touch "${MNTPOINT}"/README
+
+TEST_CASE="case 1 - README and readme differ by default"
+check "$TEST_CASE" "os.close(os.open('${MNTPOINT}/readme', os.O_CREAT | os.O_EXCL)) is None"
+
+TEST_CASE="case 2 - lookup is not a prefix match"
+check_errno "$TEST_CASE" ENOENT "os.stat('${MNTPOINT}/READ')"
+
+umount_sfs
+clean_ddriver
+mount_sfs --case-insensitive
+touch "${MNTPOINT}"/README
+
+TEST_CASE="case 3 - readme collides with README"
+check_errno "$TEST_CASE" EEXIST "os.open('${MNTPOINT}/readme', os.O_CREAT | os.O_EXCL)"
+
+TEST_CASE="case 4 - lookup ignores case, display keeps it"
+check "$TEST_CASE" "os.stat('${MNTPOINT}/readme') and os.listdir('${MNTPOINT}') == ['README']"
+
+umount_sfs
@@ -0,0 +1,26 @@
+#!/bin/bash
+# 磁盘上的目录inode损坏时返回EIO, 而不是读出错误的目录项
+# SFS_INO_OFS(ino) = 1024 + ino * 17 * 512, inode_d中dir_cnt偏移136, dentry_d中ino偏移132
+DISK="$HOME"/ddriver
+
+mount_sfs
+mkdir "${MNTPOINT}"/dir0 "${MNTPOINT}"/dir1
+touch "${MNTPOINT}"/dir1/file0
+umount_sfs
+
+py "f = open('${DISK}', 'r+b')
+f.seek(1024 + 1 * 8704 + 136); f.write((10000).to_bytes(4, 'little'))
+f.seek(1024 + 2 * 8704 + 512 + 132); f.write((100000).to_bytes(4, 'little'))
+f.close()"
+mount_sfs
+
+TEST_CASE="corrupt 1 - dir_cnt larger than the data blocks"
+check_errno "$TEST_CASE" EIO "os.stat('${MNTPOINT}/dir0')"
+
+TEST_CASE="corrupt 2 - dentry pointing past max_ino"
+check_errno "$TEST_CASE" EIO "os.stat('${MNTPOINT}/dir1')"
+
+TEST_CASE="corrupt 3 - rest of the tree still readable"
+check "$TEST_CASE" "sorted(os.listdir('${MNTPOINT}')) == ['dir0', 'dir1']"
+
+umount_sfs
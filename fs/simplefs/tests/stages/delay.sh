@@ -0,0 +1,33 @@
+#!/bin/bash
+# --write-delay-ms/--read-delay-ms: 每传输一个IO块(512B)延时N毫秒
+
+# elapsed STMT: 输出执行STMT耗费的秒数
+elapsed() {
+    python3 -c "import time
+t = time.monotonic()
+$1
+print(time.monotonic() - t)"
+}
+
+mount_sfs --write-delay-ms=100
+touch "${MNTPOINT}"/file0
+
+TEST_CASE="delay 1 - throttled write of 8 units"
+T=$(elapsed "open('${MNTPOINT}/file0', 'wb').write(b'a' * 4096)")
+check "$TEST_CASE" "${T} >= 0.8"
+
+umount_sfs
+mount_sfs --read-delay-ms=100
+
+TEST_CASE="delay 2 - throttled read of 8 units"
+T=$(elapsed "open('${MNTPOINT}/file0', 'rb').read()")
+check "$TEST_CASE" "${T} >= 0.8"
+
+umount_sfs
+mount_sfs
+
+TEST_CASE="delay 3 - no delay by default"
+T=$(elapsed "open('${MNTPOINT}/file0', 'rb').read()")
+check "$TEST_CASE" "${T} < 0.8"
+
+umount_sfs
@@ -0,0 +1,26 @@
+#!/bin/bash
+# 目录项: 文件名长度, 非空目录, remount后readdir顺序不变
+
+mount_sfs
+
+TEST_CASE="dentry 1 - name of SFS_MAX_FILE_NAME bytes"
+check_errno "$TEST_CASE" ENAMETOOLONG "open('${MNTPOINT}/' + 'n' * 128, 'w')"
+
+TEST_CASE="dentry 2 - longest valid name"
+check "$TEST_CASE" "open('${MNTPOINT}/' + 'k' * 127, 'w') and True"
+
+mkdir "${MNTPOINT}"/dir0
+touch "${MNTPOINT}"/dir0/file0
+
+TEST_CASE="dentry 3 - rmdir non-empty directory"
+check_errno "$TEST_CASE" ENOTEMPTY "os.rmdir('${MNTPOINT}/dir0')"
+
+touch "${MNTPOINT}"/file1 "${MNTPOINT}"/file2 "${MNTPOINT}"/file3
+ORDER=$(ls -U "${MNTPOINT}" | tr '\n' ' ')
+umount_sfs
+mount_sfs
+
+TEST_CASE="dentry 4 - readdir order stable across remount"
+check "$TEST_CASE" "' '.join(os.listdir('${MNTPOINT}')) + ' ' == '${ORDER}'"
+
+umount_sfs
@@ -0,0 +1,32 @@
+#!/bin/bash
+# 目录容量: 数据块最多容纳SFS_MAX_DENTRY_CNT()个目录项 (16 * 512 / 136 = 60)
+MAX_DENTRY=60
+
+mount_sfs
+mkdir "${MNTPOINT}"/dir0
+for i in $(seq 1 ${MAX_DENTRY}); do touch "${MNTPOINT}"/dir0/file$i; done
+
+TEST_CASE="dirfull 1 - create in a full directory"
+check_errno "$TEST_CASE" ENOSPC "open('${MNTPOINT}/dir0/extra', 'w')"
+
+TEST_CASE="dirfull 2 - mkdir in a full directory"
+check_errno "$TEST_CASE" ENOSPC "os.mkdir('${MNTPOINT}/dir0/extra')"
+
+TEST_CASE="dirfull 3 - rename within a full directory"
+check "$TEST_CASE" "os.rename('${MNTPOINT}/dir0/file1', '${MNTPOINT}/dir0/renamed') or os.path.exists('${MNTPOINT}/dir0/renamed')"
+
+touch "${MNTPOINT}"/file0
+TEST_CASE="dirfull 4 - rename into a full directory"
+check_errno "$TEST_CASE" ENOSPC "os.rename('${MNTPOINT}/file0', '${MNTPOINT}/dir0/file0')"
+
+umount_sfs
+mount_sfs
+
+TEST_CASE="dirfull 5 - full directory intact after remount"
+check "$TEST_CASE" "len(os.listdir('${MNTPOINT}/dir0')) == ${MAX_DENTRY}"
+
+rm "${MNTPOINT}"/dir0/file2
+TEST_CASE="dirfull 6 - create after freeing an entry"
+check "$TEST_CASE" "open('${MNTPOINT}/dir0/extra', 'w') and True"
+
+umount_sfs
@@ -0,0 +1,16 @@
+#!/bin/bash
+# --fault-inject=N: N%的write返回EIO, 守护进程不受影响
+
+mount_sfs --fault-inject=100
+touch "${MNTPOINT}"/file0
+
+TEST_CASE="fault 1 - every write fails with EIO"
+check_errno "$TEST_CASE" EIO "fd = os.open('${MNTPOINT}/file0', os.O_WRONLY); os.write(fd, b'a')"
+
+TEST_CASE="fault 2 - file left unchanged"
+check "$TEST_CASE" "os.stat('${MNTPOINT}/file0').st_size == 0"
+
+TEST_CASE="fault 3 - daemon still serving"
+check "$TEST_CASE" "os.listdir('${MNTPOINT}') == ['file0']"
+
+umount_sfs
@@ -0,0 +1,12 @@
+#!/bin/bash
+# --fixed-time: 所有时间戳报告为给定的epoch秒数
+
+mount_sfs --fixed-time=1234567890
+mkdir "${MNTPOINT}"/dir0
+echo -n "data" > "${MNTPOINT}"/dir0/file0
+touch "${MNTPOINT}"/dir0/file0
+
+TEST_CASE="fixtime 1 - every timestamp is the configured time"
+check "$TEST_CASE" "all((s.st_atime, s.st_mtime, s.st_ctime) == (1234567890,) * 3 for s in map(os.stat, ['${MNTPOINT}', '${MNTPOINT}/dir0', '${MNTPOINT}/dir0/file0']))"
+
+umount_sfs
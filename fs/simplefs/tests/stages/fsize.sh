@@ -0,0 +1,27 @@
+#!/bin/bash
+# 文件大小上限: 默认为SFS_DATA_PER_FILE个块(16 * 512), 可由--max-file-size调小
+
+mount_sfs
+touch "${MNTPOINT}"/file0
+
+TEST_CASE="fsize 1 - grow to exactly the limit"
+py "fd = os.open('${MNTPOINT}/file0', os.O_WRONLY); os.pwrite(fd, b'a' * 8192, 0); os.close(fd)"
+check "$TEST_CASE" "os.stat('${MNTPOINT}/file0').st_size == 8192"
+
+TEST_CASE="fsize 2 - one byte past the limit"
+check_errno "$TEST_CASE" EFBIG "fd = os.open('${MNTPOINT}/file0', os.O_WRONLY); os.pwrite(fd, b'a', 8192)"
+
+TEST_CASE="fsize 3 - write crossing the limit is short"
+check "$TEST_CASE" "os.pwrite(os.open('${MNTPOINT}/file0', os.O_WRONLY), b'b' * 100, 8150) == 42"
+
+TEST_CASE="fsize 4 - truncate past the limit"
+check_errno "$TEST_CASE" EFBIG "os.truncate('${MNTPOINT}/file0', 8193)"
+
+umount_sfs
+mount_sfs --max-file-size=1000
+touch "${MNTPOINT}"/file1
+
+TEST_CASE="fsize 5 - --max-file-size"
+check_errno "$TEST_CASE" EFBIG "os.truncate('${MNTPOINT}/file1', 1001)"
+
+umount_sfs
@@ -0,0 +1,29 @@
+#!/bin/bash
+# inode用尽时返回ENOSPC, 且remount后仍能正确分配
+# 每个目录只放55个文件, 保证先用尽inode而不是目录项
+
+mount_sfs
+
+TEST_CASE="ialloc 1 - exhaust inodes"
+check_errno "$TEST_CASE" ENOSPC "
+for d in range(9):
+    os.mkdir('${MNTPOINT}/d%d' % d)
+    for f in range(55):
+        open('${MNTPOINT}/d%d/f%d' % (d, f), 'w').close()"
+
+rm "${MNTPOINT}"/d0/f1
+
+TEST_CASE="ialloc 2 - cursor wraps to a freed inode"
+check "$TEST_CASE" "open('${MNTPOINT}/d0/w', 'w') and True"
+
+rm "${MNTPOINT}"/d0/f0
+umount_sfs
+mount_sfs
+
+TEST_CASE="ialloc 3 - allocate freed inode after remount"
+check "$TEST_CASE" "open('${MNTPOINT}/d0/n', 'w') and True"
+
+TEST_CASE="ialloc 4 - inodes still exhausted after remount"
+check_errno "$TEST_CASE" ENOSPC "open('${MNTPOINT}/d0/m', 'w')"
+
+umount_sfs
@@ -0,0 +1,19 @@
+#!/bin/bash
+# 磁盘结构按小端存储: super块幻数, 根目录中第一个dentry的ino
+DISK="$HOME"/ddriver
+
+mount_sfs
+touch "${MNTPOINT}"/file0
+umount_sfs
+
+TEST_CASE="layout 1 - super magic little endian"
+check "$TEST_CASE" "open('$DISK', 'rb').read(4) == (0x52415453).to_bytes(4, 'little')"
+
+# | Super(512) | Inode Map(512) | Root Inode(512) | Root Data ... |, dentry = fname[128] + ftype + ino
+TEST_CASE="layout 2 - dentry ino little endian"
+check "$TEST_CASE" "open('$DISK', 'rb').read()[1536 + 132:1536 + 136] == (1).to_bytes(4, 'little')"
+
+mount_sfs
+TEST_CASE="layout 3 - remount reads image back"
+check "$TEST_CASE" "os.listdir('${MNTPOINT}') == ['file0']"
+umount_sfs
@@ -0,0 +1,32 @@
+#!/bin/bash
+# 目录的st_nlink = 2 + 子目录数, 随机mkdir/rmdir/rename后保持不变
+
+mount_sfs
+
+TEST_CASE="nlink 1 - invariant under random directory operations"
+RES=$(py "import random
+random.seed(2502)
+dirs, n, ok = ['${MNTPOINT}'], 0, True
+for _ in range(200):
+    op = random.choice(['mkdir', 'mkdir', 'rmdir', 'rename'])
+    leaves = [d for d in dirs[1:] if not os.listdir(d)]
+    if op == 'mkdir':
+        d = os.path.join(random.choice(dirs), 'd%d' % n); n += 1
+        os.mkdir(d); dirs.append(d)
+    elif op == 'rmdir' and leaves:
+        d = random.choice(leaves); os.rmdir(d); dirs.remove(d)
+    elif op == 'rename' and leaves:
+        d = random.choice(leaves)
+        t = os.path.join(random.choice([x for x in dirs if x != d]), 'd%d' % n); n += 1
+        os.rename(d, t); dirs[dirs.index(d)] = t
+    ok = ok and all(os.stat(r).st_nlink == 2 + len(ds) for r, ds, _ in os.walk(dirs[0]))
+print(ok)")
+check "$TEST_CASE" "${RES}"
+
+umount_sfs
+mount_sfs
+
+TEST_CASE="nlink 2 - invariant after remount"
+check "$TEST_CASE" "all(os.stat(r).st_nlink == 2 + len(ds) for r, ds, _ in os.walk('${MNTPOINT}'))"
+
+umount_sfs
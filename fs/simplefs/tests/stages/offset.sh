@@ -0,0 +1,22 @@
+#!/bin/bash
+# 超大偏移: 写和truncate返回EFBIG, 读到0字节, 不触及数据块之外
+
+mount_sfs
+echo -n "hello" > "${MNTPOINT}"/file0
+
+TEST_CASE="offset 1 - write at 2^60"
+check_errno "$TEST_CASE" EFBIG "fd = os.open('${MNTPOINT}/file0', os.O_WRONLY); os.pwrite(fd, b'x', 2 ** 60)"
+
+TEST_CASE="offset 2 - truncate to 2^60"
+check_errno "$TEST_CASE" EFBIG "os.truncate('${MNTPOINT}/file0', 2 ** 60)"
+
+TEST_CASE="offset 3 - read at 2^60"
+check "$TEST_CASE" "os.pread(os.open('${MNTPOINT}/file0', os.O_RDONLY), 10, 2 ** 60) == b''"
+
+TEST_CASE="offset 4 - read stops at end of file"
+check "$TEST_CASE" "os.pread(os.open('${MNTPOINT}/file0', os.O_RDONLY), 4096, 2) == b'llo'"
+
+TEST_CASE="offset 5 - file unchanged"
+check "$TEST_CASE" "os.stat('${MNTPOINT}/file0').st_size == 5"
+
+umount_sfs
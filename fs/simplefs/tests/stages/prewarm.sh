@@ -0,0 +1,22 @@
+#!/bin/bash
+# --prewarm: 挂载时读入所有inode, 之后的stat不再读盘
+# 挂载后改写磁盘上dir0的dir_cnt, 若stat仍读盘则返回EIO (见corrupt.sh)
+DISK="$HOME"/ddriver
+
+mount_sfs
+mkdir "${MNTPOINT}"/dir0
+touch "${MNTPOINT}"/dir0/file0
+umount_sfs
+
+mount_sfs --prewarm
+py "f = open('${DISK}', 'r+b')
+f.seek(1024 + 1 * 8704 + 136); f.write((10000).to_bytes(4, 'little'))
+f.close()"
+
+TEST_CASE="prewarm 1 - stat served from memory"
+check "$TEST_CASE" "os.stat('${MNTPOINT}/dir0/file0').st_size == 0"
+
+TEST_CASE="prewarm 2 - directory served from memory"
+check "$TEST_CASE" "os.listdir('${MNTPOINT}/dir0') == ['file0']"
+
+umount_sfs
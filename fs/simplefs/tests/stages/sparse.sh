@@ -0,0 +1,23 @@
+#!/bin/bash
+# 空洞与扩展部分读出为0
+
+mount_sfs
+touch "${MNTPOINT}"/file0 "${MNTPOINT}"/file1
+
+TEST_CASE="sparse 1 - write past end of file"
+py "fd = os.open('${MNTPOINT}/file0', os.O_WRONLY); os.pwrite(fd, b'x', 4096); os.close(fd)"
+check "$TEST_CASE" "open('${MNTPOINT}/file0', 'rb').read() == bytes(4096) + b'x'"
+
+TEST_CASE="sparse 2 - truncate shrink then grow"
+echo -n "abcdef" > "${MNTPOINT}"/file1
+truncate -s 2 "${MNTPOINT}"/file1
+truncate -s 6 "${MNTPOINT}"/file1
+check "$TEST_CASE" "open('${MNTPOINT}/file1', 'rb').read() == b'ab' + bytes(4)"
+
+umount_sfs
+mount_sfs
+
+TEST_CASE="sparse 3 - zeros survive remount"
+check "$TEST_CASE" "open('${MNTPOINT}/file1', 'rb').read() == b'ab' + bytes(4)"
+
+umount_sfs
@@ -0,0 +1,26 @@
+#!/bin/bash
+# statfs: 空闲inode计数随分配/释放精确变化, remount后一致
+
+mount_sfs
+FREE=$(python3 -c "import os; print(os.statvfs('${MNTPOINT}').f_ffree)")
+
+touch "${MNTPOINT}"/file0
+mkdir "${MNTPOINT}"/dir0
+TEST_CASE="statfs 1 - create file and directory"
+check "$TEST_CASE" "os.statvfs('${MNTPOINT}').f_ffree == ${FREE} - 2"
+
+rmdir "${MNTPOINT}"/dir0
+TEST_CASE="statfs 2 - rmdir frees the inode"
+check "$TEST_CASE" "os.statvfs('${MNTPOINT}').f_ffree == ${FREE} - 1"
+
+umount_sfs
+mount_sfs
+
+TEST_CASE="statfs 3 - counter after remount"
+check "$TEST_CASE" "os.statvfs('${MNTPOINT}').f_ffree == ${FREE} - 1"
+
+rm "${MNTPOINT}"/file0
+TEST_CASE="statfs 4 - unlink frees the inode"
+check "$TEST_CASE" "os.statvfs('${MNTPOINT}').f_ffree == ${FREE}"
+
+umount_sfs
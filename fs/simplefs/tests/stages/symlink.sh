@@ -0,0 +1,16 @@
+#!/bin/bash
+# 软链接目标长度受target_path(SFS_MAX_FILE_NAME)限制
+
+mount_sfs
+
+TEST_CASE="symlink 1 - 5000-byte target"
+check_errno "$TEST_CASE" ENAMETOOLONG "os.symlink('t' * 5000, '${MNTPOINT}/link0')"
+
+TEST_CASE="symlink 2 - rejected link is not created"
+check "$TEST_CASE" "not os.path.lexists('${MNTPOINT}/link0')"
+
+TEST_CASE="symlink 3 - longest valid target"
+py "os.symlink('t' * 127, '${MNTPOINT}/link1')"
+check "$TEST_CASE" "os.readlink('${MNTPOINT}/link1') == 't' * 127"
+
+umount_sfs
@@ -0,0 +1,13 @@
+#!/bin/bash
+# --verify-writes: 元数据读回校验, 正常介质上不影响写回
+
+mount_sfs --verify-writes
+mkdir "${MNTPOINT}"/dir0
+echo -n "hello" > "${MNTPOINT}"/dir0/file0
+umount_sfs
+mount_sfs
+
+TEST_CASE="verify 1 - verified write-back survives remount"
+check "$TEST_CASE" "open('${MNTPOINT}/dir0/file0').read() == 'hello'"
+
+umount_sfs
@@ -0,0 +1,103 @@
+#!/bin/bash
+# SFS 边界测试: 依次执行 stages/ 下的用例, 每个用例开始前重置 ddriver
+POINTS=0
+TOTAL_POINTS=0
+ROOT_PATH=$(cd "$(dirname "$0")" && pwd)
+MNTPOINT="$ROOT_PATH"/mnt
+PROJECT_NAME="sfs-fuse"
+
+# 编译src
+cd "$ROOT_PATH"/..; mkdir build >/dev/null 2>&1; cd build || exit
+
+cmake .. >/dev/null 2>&1; make >/dev/null 2>&1;
+if [ $? -eq 0 ]; then
+    echo "" >/dev/null
+else
+    echo "Test Fail : 编译失败"
+    exit 1
+fi
+cd "$ROOT_PATH" || exit
+
+rm mnt -rf
+mkdir mnt 2>/dev/null
+
+# Helpers
+function pass() {
+    RES=$1
+    POINTS=$((POINTS + 1))
+    echo -e "\033[32mpass: ${RES}\033[0m"
+}
+
+function fail() {
+    RES=$1
+    echo -e "\033[31mfail: ${RES}\033[0m"
+}
+
+function clean_ddriver() {
+    sleep 1
+    ddriver -r > /dev/null
+}
+
+# mount_sfs [sfs options...]
+function mount_sfs() {
+    "$ROOT_PATH"/../build/"${PROJECT_NAME}" --device="$HOME"/ddriver "$@" "${MNTPOINT}"
+}
+
+function umount_sfs() {
+    sleep 1
+    fusermount -u "${MNTPOINT}"
+    while pgrep -x "${PROJECT_NAME}" > /dev/null; do   # 等待destroy写回磁盘
+        sleep 0.1
+    done
+}
+
+# py STMT: 执行一段python语句 (已导入os)
+function py() {
+    python3 -c "import os
+$1"
+}
+
+# check TEST_CASE EXPR: python表达式EXPR为真则通过
+function check() {
+    TOTAL_POINTS=$((TOTAL_POINTS + 1))
+    if python3 -c "import os, sys; sys.exit(0 if ($2) else 1)"; then
+        pass "$1"
+    else
+        fail "$1"
+    fi
+}
+
+# check_errno TEST_CASE ERRNO STMT: python语句STMT应失败并返回ERRNO
+function check_errno() {
+    TOTAL_POINTS=$((TOTAL_POINTS + 1))
+    if python3 - "$2" "$3" <<'EOF'
+import errno, os, sys
+try:
+    exec(sys.argv[2])
+except OSError as e:
+    sys.exit(0 if e.errno == getattr(errno, sys.argv[1]) else 1)
+sys.exit(1)
+EOF
+    then
+        pass "$1"
+    else
+        fail "$1: 期望返回 $2"
+    fi
+}
+
+# Test
+fusermount -u "${MNTPOINT}" >/dev/null 2>&1
+for stage in "$ROOT_PATH"/stages/*.sh; do
+    echo "测试用例: $stage"
+    clean_ddriver
+    # shellcheck source=/dev/null
+    source "$stage"
+done
+
+echo "Score: $POINTS/$TOTAL_POINTS"
+if [ $POINTS -eq $TOTAL_POINTS ]; then
+    pass "通过所有测试"
+else
+    fail "存在未通过的测试"
+    exit 1
+fi